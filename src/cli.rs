@@ -0,0 +1,133 @@
+use rcue::PumpMode;
+
+use crate::{PID, VID};
+
+/// Default listen address for `usbip-server`, matching the port the
+/// real USB/IP kernel protocol uses (this crate's wire format is not
+/// that protocol, see `rcue::usbip::serve`, but the port is a familiar
+/// default).
+const DEFAULT_USBIP_ADDR: &str = "0.0.0.0:3240";
+
+/// Parsed command line invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cli {
+    /// Read a single telemetry report and print it.
+    Read { vid: u16, pid: u16 },
+    /// Continuously poll and print telemetry reports.
+    Monitor { vid: u16, pid: u16 },
+    /// Enumerate connected USB devices, flagging Corsair ones.
+    List,
+    /// Set a fan channel's target duty cycle, in percent.
+    SetFan {
+        channel: u8,
+        percent: u8,
+        vid: u16,
+        pid: u16,
+    },
+    /// Set the pump's operating mode.
+    SetPump {
+        mode: PumpMode,
+        vid: u16,
+        pid: u16,
+    },
+    /// Serve the cooler to a remote client over `rcue::usbip::serve`'s
+    /// TCP protocol.
+    UsbipServer { addr: String, vid: u16, pid: u16 },
+}
+
+impl Cli {
+    /// Parses `std::env::args()` (minus the binary name) into a `Cli`.
+    ///
+    /// With no arguments this defaults to `Read` against the built-in
+    /// `VID`/`PID`, preserving the original one-shot behaviour.
+    pub fn parse() -> Self {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+
+        match args.first().map(String::as_str) {
+            Some("list") => Cli::List,
+            Some("monitor") => {
+                let (vid, pid) = parse_vid_pid(&args[1..]);
+                Cli::Monitor { vid, pid }
+            }
+            Some("read") => {
+                let (vid, pid) = parse_vid_pid(&args[1..]);
+                Cli::Read { vid, pid }
+            }
+            Some("set-fan") => {
+                let channel = args
+                    .get(1)
+                    .and_then(|s| s.parse().ok())
+                    .expect("usage: set-fan <channel> <percent> [vid] [pid]");
+                let percent = args
+                    .get(2)
+                    .and_then(|s| s.parse().ok())
+                    .expect("usage: set-fan <channel> <percent> [vid] [pid]");
+                let (vid, pid) = parse_vid_pid(&args[3..]);
+                Cli::SetFan {
+                    channel,
+                    percent,
+                    vid,
+                    pid,
+                }
+            }
+            Some("set-pump") => {
+                let mode = args
+                    .get(1)
+                    .and_then(|s| s.parse().ok())
+                    .expect("usage: set-pump <quiet|balanced|performance> [vid] [pid]");
+                let (vid, pid) = parse_vid_pid(&args[2..]);
+                Cli::SetPump { mode, vid, pid }
+            }
+            Some("usbip-server") => {
+                let addr = args
+                    .get(1)
+                    .cloned()
+                    .unwrap_or_else(|| DEFAULT_USBIP_ADDR.to_string());
+                let (vid, pid) = parse_vid_pid(&args[2..]);
+                Cli::UsbipServer { addr, vid, pid }
+            }
+            _ => Cli::Read { vid: VID, pid: PID },
+        }
+    }
+}
+
+fn parse_vid_pid(args: &[String]) -> (u16, u16) {
+    let vid = args
+        .first()
+        .and_then(|s| convert_argument(s))
+        .unwrap_or(VID);
+    let pid = args
+        .get(1)
+        .and_then(|s| convert_argument(s))
+        .unwrap_or(PID);
+    (vid, pid)
+}
+
+/// Parses a command line argument as either a `0x`-prefixed hex number
+/// or a plain decimal number.
+fn convert_argument(arg: &str) -> Option<u16> {
+    match arg.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16).ok(),
+        None => arg.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_argument_accepts_hex() {
+        assert_eq!(convert_argument("0x1b1c"), Some(0x1b1c));
+    }
+
+    #[test]
+    fn convert_argument_accepts_decimal() {
+        assert_eq!(convert_argument("6940"), Some(6940));
+    }
+
+    #[test]
+    fn convert_argument_rejects_garbage() {
+        assert_eq!(convert_argument("not a number"), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! Write-command reports: fan duty and pump mode control.
+
+use rusb::{DeviceHandle, Result, UsbContext};
+
+use crate::device;
+
+/// Pump operating modes supported by the write-command report.
+///
+/// The opcodes below are picked directly from captures of the vendor
+/// Windows software and mirror the values `set_pump_mode` writes into
+/// the command report's payload byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PumpMode {
+    Quiet,
+    Balanced,
+    Performance,
+}
+
+impl PumpMode {
+    fn as_byte(self) -> u8 {
+        match self {
+            PumpMode::Quiet => 0x00,
+            PumpMode::Balanced => 0x01,
+            PumpMode::Performance => 0x02,
+        }
+    }
+}
+
+impl std::str::FromStr for PumpMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "quiet" => Ok(PumpMode::Quiet),
+            "balanced" => Ok(PumpMode::Balanced),
+            "performance" => Ok(PumpMode::Performance),
+            other => Err(format!(
+                "unknown pump mode {:?}, expected quiet, balanced or performance",
+                other
+            )),
+        }
+    }
+}
+
+/// Command opcodes understood by `write_command`.
+///
+/// Leading byte of the report after the `0x3f` report id, matching the
+/// layout `device::set_report` already uses for the initial "start"
+/// command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    SetFanDuty { channel: u8, percent: u8 },
+    SetPumpMode(PumpMode),
+}
+
+impl Command {
+    const OP_SET_FAN_DUTY: u8 = 0x20;
+    const OP_SET_PUMP_MODE: u8 = 0x30;
+
+    /// Encodes this command into a 64-byte write-command report.
+    ///
+    /// UNVERIFIED: `device::set_report`'s captured "start" payload ends
+    /// with a non-zero trailing byte (`0x5b`), which may be a required
+    /// trailer or checksum rather than incidental padding. Nobody has
+    /// confirmed against a capture whether write-command reports need
+    /// the same trailing byte, so byte 63 is left at `0` here. Treat
+    /// fan/pump commands with suspicion until someone checks a capture.
+    fn encode(self, report: &mut [u8; 64]) {
+        report[0] = 0x3f;
+        match self {
+            Command::SetFanDuty { channel, percent } => {
+                report[1] = Self::OP_SET_FAN_DUTY;
+                report[2] = channel;
+                report[3] = percent;
+            }
+            Command::SetPumpMode(mode) => {
+                report[1] = Self::OP_SET_PUMP_MODE;
+                report[2] = mode.as_byte();
+            }
+        }
+    }
+}
+
+/// Writes a single command report to the device.
+///
+/// Uses the same control transfer shape as `device::set_report`
+/// (`REQUEST_TYPE` `0x21`, `REQUEST` `0x09`), since the device accepts
+/// both the initial "start" report and subsequent command reports
+/// through the same HID SET_REPORT request.
+fn write_command<T: UsbContext>(handle: &mut DeviceHandle<T>, command: Command) -> Result<usize> {
+    const REQEST_TYPE: u8 = 0x21;
+    const REQUEST: u8 = 0x09;
+    const VALUE: u16 = 0x0200;
+    const INDEX: u16 = 0x0000;
+
+    let mut report = [0u8; 64];
+    command.encode(&mut report);
+
+    device::write_control(handle, REQEST_TYPE, REQUEST, VALUE, INDEX, &report)
+}
+
+/// Sets the target fan duty cycle, in percent, for the given fan channel.
+pub fn set_fan_duty<T: UsbContext>(
+    handle: &mut DeviceHandle<T>,
+    channel: u8,
+    percent: u8,
+) -> Result<usize> {
+    write_command(handle, Command::SetFanDuty { channel, percent })
+}
+
+/// Sets the pump's operating mode.
+pub fn set_pump_mode<T: UsbContext>(handle: &mut DeviceHandle<T>, mode: PumpMode) -> Result<usize> {
+    write_command(handle, Command::SetPumpMode(mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_set_fan_duty() {
+        let mut report = [0u8; 64];
+        Command::SetFanDuty {
+            channel: 2,
+            percent: 75,
+        }
+        .encode(&mut report);
+
+        assert_eq!(report[0], 0x3f);
+        assert_eq!(report[1], Command::OP_SET_FAN_DUTY);
+        assert_eq!(report[2], 2);
+        assert_eq!(report[3], 75);
+    }
+
+    #[test]
+    fn encode_set_pump_mode() {
+        let mut report = [0u8; 64];
+        Command::SetPumpMode(PumpMode::Performance).encode(&mut report);
+
+        assert_eq!(report[0], 0x3f);
+        assert_eq!(report[1], Command::OP_SET_PUMP_MODE);
+        assert_eq!(report[2], PumpMode::Performance.as_byte());
+    }
+}
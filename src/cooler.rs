@@ -0,0 +1,116 @@
+//! A reusable handle to an open cooler, so downstream crates can embed
+//! cooler control instead of going through the CLI binary.
+
+use rusb::{Context, DeviceHandle, UsbContext};
+
+use crate::command::{self, PumpMode};
+use crate::device::{self, Endpoint};
+use crate::error::{Error, Result};
+use crate::profile::{self, ReportLayout};
+use crate::report::CoolerReport;
+
+/// An open connection to a Corsair cooler.
+///
+/// `Cooler::open` performs discovery, claims the device's first
+/// readable endpoint and issues the same idle/start reports `main`
+/// used to send by hand. Dropping a `Cooler` releases the interface
+/// and reattaches the kernel driver if `open` detached it, so callers
+/// don't need to remember to clean up after themselves.
+pub struct Cooler<T: UsbContext = Context> {
+    handle: DeviceHandle<T>,
+    endpoint: Endpoint,
+    has_kernel_driver: bool,
+    layout: ReportLayout,
+}
+
+impl Cooler<Context> {
+    /// Opens the first device matching `vid`/`pid` on the default
+    /// `rusb` context.
+    pub fn open(vid: u16, pid: u16) -> Result<Self> {
+        let mut context = Context::new()?;
+        Cooler::open_with_context(&mut context, vid, pid)
+    }
+}
+
+impl<T: UsbContext> Cooler<T> {
+    /// Opens the first device matching `vid`/`pid` on `context`.
+    pub fn open_with_context(context: &mut T, vid: u16, pid: u16) -> Result<Self> {
+        let (mut raw_device, mut handle) = device::open_device(context, vid, pid)
+            .ok_or(Error::Usb(rusb::Error::NoDevice))?;
+
+        let endpoint = *device::find_readable_endpoints(&mut raw_device)?
+            .first()
+            .ok_or(Error::Usb(rusb::Error::NotFound))?;
+
+        let has_kernel_driver = match handle.kernel_driver_active(endpoint.iface) {
+            Ok(true) => {
+                handle.detach_kernel_driver(endpoint.iface)?;
+                true
+            }
+            _ => false,
+        };
+
+        device::configure_endpoint(&mut handle, &endpoint)?;
+        device::set_idle(&mut handle).ok();
+        device::set_report(&mut handle)?;
+
+        Ok(Cooler {
+            handle,
+            endpoint,
+            has_kernel_driver,
+            layout: profile::layout_for_pid(pid),
+        })
+    }
+
+    /// Reads and parses one telemetry report.
+    pub fn read_report(&mut self) -> Result<CoolerReport> {
+        let data = self.read_raw()?;
+        Ok(CoolerReport::parse(&data, &self.layout)?)
+    }
+
+    /// Sets the target fan duty cycle, in percent, for the given fan channel.
+    pub fn set_fan_duty(&mut self, channel: u8, percent: u8) -> Result<()> {
+        command::set_fan_duty(&mut self.handle, channel, percent)?;
+        Ok(())
+    }
+
+    /// Sets the pump's operating mode.
+    pub fn set_pump_mode(&mut self, mode: PumpMode) -> Result<()> {
+        command::set_pump_mode(&mut self.handle, mode)?;
+        Ok(())
+    }
+
+    /// Reads a raw 64-byte interrupt report, without parsing it.
+    ///
+    /// Exposed for callers like the `usbip` module that forward bytes
+    /// to a remote client rather than consuming a `CoolerReport`.
+    pub fn read_raw(&mut self) -> rusb::Result<Vec<u8>> {
+        device::read_interrupt(&mut self.handle, self.endpoint.address)
+    }
+
+    /// Proxies an arbitrary control OUT transfer to the device.
+    pub fn write_control_raw(
+        &mut self,
+        request_type: u8,
+        request: u8,
+        value: u16,
+        index: u16,
+        data: &[u8],
+    ) -> rusb::Result<usize> {
+        device::write_control(&mut self.handle, request_type, request, value, index, data)
+    }
+
+    /// The report layout this cooler was opened with.
+    pub fn layout(&self) -> &ReportLayout {
+        &self.layout
+    }
+}
+
+impl<T: UsbContext> Drop for Cooler<T> {
+    fn drop(&mut self) {
+        let _ = self.handle.release_interface(self.endpoint.iface);
+        if self.has_kernel_driver {
+            let _ = self.handle.attach_kernel_driver(self.endpoint.iface);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Low-level HID transport: device discovery, endpoint configuration
+//! and the raw control/interrupt transfers the device understands.
+
+use rusb::{Device, DeviceHandle, Result, UsbContext};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Endpoint {
+    pub config: u8,
+    pub iface: u8,
+    pub setting: u8,
+    pub address: u8,
+}
+
+pub fn open_device<T: UsbContext>(
+    context: &mut T,
+    vid: u16,
+    pid: u16,
+) -> Option<(Device<T>, DeviceHandle<T>)> {
+    let devices = match context.devices() {
+        Ok(d) => d,
+        Err(_) => return None,
+    };
+
+    for device in devices.iter() {
+        let device_desc = match device.device_descriptor() {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+
+        if device_desc.vendor_id() == vid && device_desc.product_id() == pid {
+            match device.open() {
+                Ok(handle) => return Some((device, handle)),
+                Err(_) => continue,
+            }
+        }
+    }
+
+    None
+}
+
+pub fn print_device_info<T: UsbContext>(handle: &mut DeviceHandle<T>) -> Result<()> {
+    let device_desc = handle.device().device_descriptor()?;
+    let timeout = Duration::from_secs(1);
+    let languages = handle.read_languages(timeout)?;
+
+    println!("Active configuration: {}", handle.active_configuration()?);
+
+    if !languages.is_empty() {
+        let language = languages[0];
+        println!("Language: {:?}", language);
+
+        println!(
+            "Manufacturer: {}",
+            handle
+                .read_manufacturer_string(language, &device_desc, timeout)
+                .unwrap_or_else(|_| "Not Found".to_string())
+        );
+        println!(
+            "Product: {}",
+            handle
+                .read_product_string(language, &device_desc, timeout)
+                .unwrap_or_else(|_| "Not Found".to_string())
+        );
+        println!(
+            "Serial Number: {}",
+            handle
+                .read_serial_number_string(language, &device_desc, timeout)
+                .unwrap_or_else(|_| "Not Found".to_string())
+        );
+    }
+    Ok(())
+}
+
+/// Returns all readable endpoints for the given USB device.
+pub fn find_readable_endpoints<T: UsbContext>(device: &mut Device<T>) -> Result<Vec<Endpoint>> {
+    let device_desc = device.device_descriptor()?;
+    let mut endpoints = vec![];
+    for n in 0..device_desc.num_configurations() {
+        let config_desc = match device.config_descriptor(n) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        for interface in config_desc.interfaces() {
+            for interface_desc in interface.descriptors() {
+                for endpoint_desc in interface_desc.endpoint_descriptors() {
+                    endpoints.push(Endpoint {
+                        config: config_desc.number(),
+                        iface: interface_desc.interface_number(),
+                        setting: interface_desc.setting_number(),
+                        address: endpoint_desc.address(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(endpoints)
+}
+
+pub fn configure_endpoint<T: UsbContext>(
+    handle: &mut DeviceHandle<T>,
+    endpoint: &Endpoint,
+) -> Result<()> {
+    handle.set_active_configuration(endpoint.config)?;
+    handle.claim_interface(endpoint.iface)?;
+    handle.set_alternate_setting(endpoint.iface, endpoint.setting)
+}
+
+pub fn set_idle<T: UsbContext>(handle: &mut DeviceHandle<T>) -> Result<usize> {
+    let timeout = Duration::from_secs(1);
+    const REQEST_TYPE: u8 = 0x21;
+    const REQUEST: u8 = 0x0A;
+    const VALUE: u16 = 0x0000;
+    const INDEX: u16 = 0x0000;
+    // set IDLE request
+    handle.write_control(REQEST_TYPE, REQUEST, VALUE, INDEX, &[], timeout)
+}
+
+pub fn set_report<T: UsbContext>(handle: &mut DeviceHandle<T>) -> Result<usize> {
+    let timeout = Duration::from_secs(1);
+
+    // values are picked directly from the captured packet
+    const REQEST_TYPE: u8 = 0x21;
+    const REQUEST: u8 = 0x09;
+    const VALUE: u16 = 0x0200;
+    const INDEX: u16 = 0x0000;
+    const DATA: [u8; 64] = [
+        0x3f, 0x10, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x5b,
+    ];
+
+    handle.write_control(REQEST_TYPE, REQUEST, VALUE, INDEX, &DATA, timeout)
+}
+
+/// Writes an arbitrary control OUT transfer, the same request shape
+/// `set_idle`/`set_report` use. Exposed so callers (the `command` and
+/// `usbip` modules) can build their own report payloads.
+pub fn write_control<T: UsbContext>(
+    handle: &mut DeviceHandle<T>,
+    request_type: u8,
+    request: u8,
+    value: u16,
+    index: u16,
+    data: &[u8],
+) -> Result<usize> {
+    let timeout = Duration::from_secs(1);
+    handle.write_control(request_type, request, value, index, data, timeout)
+}
+
+pub fn read_interrupt<T: UsbContext>(handle: &mut DeviceHandle<T>, address: u8) -> Result<Vec<u8>> {
+    let timeout = Duration::from_secs(1);
+    let mut buf = [0u8; 64];
+
+    handle
+        .read_interrupt(address, &mut buf, timeout)
+        .map(|_| buf.to_vec())
+}
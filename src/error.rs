@@ -0,0 +1,46 @@
+use std::fmt;
+
+use crate::report::ParseError;
+
+/// Errors a `Cooler` can return: either the USB transfer itself failed,
+/// or it succeeded but the report it returned couldn't be parsed.
+#[derive(Debug)]
+pub enum Error {
+    Usb(rusb::Error),
+    Parse(ParseError),
+    /// A transport error from something that isn't USB, e.g. the TCP
+    /// listener `usbip::serve` uses.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usb(err) => write!(f, "USB error: {}", err),
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusb::Error> for Error {
+    fn from(err: rusb::Error) -> Self {
+        Error::Usb(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,13 @@
+pub mod command;
+pub mod cooler;
+pub mod device;
+pub mod error;
+pub mod monitor;
+pub mod profile;
+pub mod report;
+pub mod usbip;
+
+pub use command::PumpMode;
+pub use cooler::Cooler;
+pub use error::{Error, Result};
+pub use report::CoolerReport;
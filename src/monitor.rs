@@ -0,0 +1,30 @@
+use rusb::UsbContext;
+use std::time::Duration;
+
+use crate::cooler::Cooler;
+use crate::error::{Error, Result};
+
+/// Repeatedly reads telemetry from `cooler` every `interval`, invoking
+/// `on_report` with each successfully parsed reading.
+///
+/// `cooler` is reused across iterations rather than reopened, so the
+/// kernel driver is only detached/reattached once, around the whole
+/// loop. Transient `rusb::Error::Timeout`s are retried instead of
+/// aborting the loop, since the device occasionally misses an
+/// interrupt window under load.
+pub fn poll_loop<T: UsbContext>(
+    cooler: &mut Cooler<T>,
+    interval: Duration,
+    mut on_report: impl FnMut(crate::report::CoolerReport),
+) -> Result<()> {
+    loop {
+        match cooler.read_report() {
+            Ok(report) => on_report(report),
+            Err(Error::Usb(rusb::Error::Timeout)) => {}
+            Err(Error::Parse(err)) => eprintln!("failed to parse telemetry report: {}", err),
+            Err(err) => return Err(err),
+        }
+
+        std::thread::sleep(interval);
+    }
+}
@@ -0,0 +1,72 @@
+/// Corsair's USB vendor id.
+pub const CORSAIR_VID: u16 = 0x1b1c;
+
+/// Field offsets within a 64-byte telemetry report.
+///
+/// Offsets are per-PID rather than hardcoded, since they're expected
+/// to differ across the H100i/H115i/H150i family the way they did
+/// between the single device this crate originally supported and
+/// whatever comes next. See the per-PID constants below for which of
+/// these are confirmed against a real capture and which are only
+/// assumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportLayout {
+    pub temp_offset: usize,
+    pub pump_offset: usize,
+    pub fan_offsets: [usize; 3],
+}
+
+/// Layout for the H100i Platinum (PID `0x0c22`), reverse engineered
+/// from the original packet capture this crate started from. The only
+/// layout in this table actually confirmed against a capture.
+const H100I_PLATINUM: ReportLayout = ReportLayout {
+    temp_offset: 7,
+    pump_offset: 29,
+    fan_offsets: [15, 22, 43],
+};
+
+/// Layout for the H115i Platinum (PID `0x0c12`).
+///
+/// UNVERIFIED: assumed identical to `H100I_PLATINUM` since it uses the
+/// same controller, but nobody has confirmed this against a capture
+/// of an actual H115i. Treat telemetry from this PID with suspicion
+/// until someone does.
+const H115I_PLATINUM: ReportLayout = H100I_PLATINUM;
+
+/// Layout for the H150i Pro (PID `0x0c08`).
+///
+/// UNVERIFIED placeholder: the third fan offset is a guess, not
+/// independently reverse engineered from a capture of an actual
+/// H150i Pro. Kept distinct from `H100I_PLATINUM` rather than aliased
+/// to it so this entry doesn't quietly start reusing a different
+/// PID's confirmed offset if that placeholder value is ever firmed up.
+const H150I_PRO: ReportLayout = ReportLayout {
+    temp_offset: 7,
+    pump_offset: 29,
+    fan_offsets: [15, 22, 50],
+};
+
+/// Known Corsair cooler PIDs and their report layout.
+const KNOWN_DEVICES: &[(u16, &str, ReportLayout)] = &[
+    (0x0c22, "H100i Platinum", H100I_PLATINUM),
+    (0x0c12, "H115i Platinum", H115I_PLATINUM),
+    (0x0c08, "H150i Pro", H150I_PRO),
+];
+
+/// Returns the report layout for a known PID, falling back to the
+/// H100i Platinum layout for unrecognised devices.
+pub fn layout_for_pid(pid: u16) -> ReportLayout {
+    KNOWN_DEVICES
+        .iter()
+        .find(|(known_pid, _, _)| *known_pid == pid)
+        .map(|(_, _, layout)| *layout)
+        .unwrap_or(H100I_PLATINUM)
+}
+
+/// Returns the marketing name for a known PID, if any.
+pub fn name_for_pid(pid: u16) -> Option<&'static str> {
+    KNOWN_DEVICES
+        .iter()
+        .find(|(known_pid, _, _)| *known_pid == pid)
+        .map(|(_, name, _)| *name)
+}
@@ -0,0 +1,157 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::{fmt, io::Cursor};
+
+use crate::profile::ReportLayout;
+
+/// Report id of a valid telemetry interrupt report, found at offset 0.
+const REPORT_ID: u8 = 0x3f;
+
+/// Length, in bytes, of a telemetry interrupt report.
+const REPORT_LEN: usize = 64;
+
+/// Error returned when a telemetry report can't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The buffer was not exactly `REPORT_LEN` bytes long.
+    UnexpectedLength(usize),
+    /// The leading byte was not the expected report id.
+    UnexpectedReportId(u8),
+    /// A `ReportLayout` offset left no room for the 2-byte field read
+    /// at that position within a `REPORT_LEN`-byte buffer.
+    OffsetOutOfRange(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedLength(len) => {
+                write!(f, "expected a {}-byte report, got {} bytes", REPORT_LEN, len)
+            }
+            ParseError::UnexpectedReportId(id) => {
+                write!(f, "expected report id {:#04x}, got {:#04x}", REPORT_ID, id)
+            }
+            ParseError::OffsetOutOfRange(offset) => {
+                write!(
+                    f,
+                    "layout offset {} leaves no room for a 2-byte field in a {}-byte report",
+                    offset, REPORT_LEN
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Telemetry reported by the cooler on its interrupt endpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoolerReport {
+    pub coolant_temp_c: f32,
+    pub pump_rpm: u16,
+    pub fan_rpm: [u16; 3],
+}
+
+impl CoolerReport {
+    /// Parses a raw 64-byte interrupt report into a `CoolerReport`,
+    /// reading each field at the offsets given by `layout` since they
+    /// differ across the H100i/H115i/H150i family.
+    pub fn parse(buf: &[u8], layout: &ReportLayout) -> Result<Self, ParseError> {
+        if buf.len() != REPORT_LEN {
+            return Err(ParseError::UnexpectedLength(buf.len()));
+        }
+        if buf[0] != REPORT_ID {
+            return Err(ParseError::UnexpectedReportId(buf[0]));
+        }
+
+        let mut rdr = Cursor::new(buf);
+
+        let coolant_temp_c = read_u16_at(&mut rdr, layout.temp_offset)? as f32 / 256.0;
+
+        let mut fan_rpm = [0u16; 3];
+        for (i, offset) in layout.fan_offsets.iter().enumerate() {
+            fan_rpm[i] = read_u16_at(&mut rdr, *offset)?;
+        }
+
+        let pump_rpm = read_u16_at(&mut rdr, layout.pump_offset)?;
+
+        Ok(CoolerReport {
+            coolant_temp_c,
+            pump_rpm,
+            fan_rpm,
+        })
+    }
+}
+
+/// Reads a little-endian `u16` at `offset`, turning the `Cursor`'s
+/// `UnexpectedEof` (an out-of-range `ReportLayout` offset) into a
+/// descriptive `ParseError` instead of letting callers `unwrap()` it.
+fn read_u16_at(rdr: &mut Cursor<&[u8]>, offset: usize) -> Result<u16, ParseError> {
+    rdr.set_position(offset as u64);
+    rdr.read_u16::<LittleEndian>()
+        .map_err(|_| ParseError::OffsetOutOfRange(offset))
+}
+
+impl fmt::Display for CoolerReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Temp : {:.2}°C", self.coolant_temp_c)?;
+        for (i, rpm) in self.fan_rpm.iter().enumerate() {
+            writeln!(f, "Fan {}: {} rpm", i + 1, rpm)?;
+        }
+        write!(f, "Pump : {} rpm", self.pump_rpm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_buf(report_id: u8) -> [u8; REPORT_LEN] {
+        let mut buf = [0u8; REPORT_LEN];
+        buf[0] = report_id;
+        buf
+    }
+
+    #[test]
+    fn parse_rejects_an_offset_too_close_to_the_end() {
+        let buf = report_buf(REPORT_ID);
+        let layout = ReportLayout {
+            temp_offset: 7,
+            pump_offset: 63,
+            fan_offsets: [15, 22, 43],
+        };
+
+        assert_eq!(
+            CoolerReport::parse(&buf, &layout),
+            Err(ParseError::OffsetOutOfRange(63))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_wrong_length() {
+        let layout = ReportLayout {
+            temp_offset: 7,
+            pump_offset: 29,
+            fan_offsets: [15, 22, 43],
+        };
+
+        assert_eq!(
+            CoolerReport::parse(&[0u8; 10], &layout),
+            Err(ParseError::UnexpectedLength(10))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_wrong_report_id() {
+        let buf = report_buf(0x00);
+        let layout = ReportLayout {
+            temp_offset: 7,
+            pump_offset: 29,
+            fan_offsets: [15, 22, 43],
+        };
+
+        assert_eq!(
+            CoolerReport::parse(&buf, &layout),
+            Err(ParseError::UnexpectedReportId(0x00))
+        );
+    }
+}
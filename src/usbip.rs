@@ -0,0 +1,260 @@
+use std::io::{self, Read, Write};
+use std::net::ToSocketAddrs;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use rusb::UsbContext;
+
+use crate::cooler::Cooler;
+use crate::error::Result;
+use crate::report::{CoolerReport, ParseError};
+
+/// A single USB Request Block forwarded to us by a USB/IP transport.
+///
+/// This mirrors the two request shapes a USB/IP gadget actually needs
+/// to forward for this device: a control OUT transfer (used for the
+/// idle/report setup and write commands) and an interrupt IN transfer
+/// (used for telemetry polling).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Urb {
+    ControlOut {
+        request_type: u8,
+        request: u8,
+        value: u16,
+        index: u16,
+        data: Vec<u8>,
+    },
+    InterruptIn,
+}
+
+/// Implemented by something that can answer URBs forwarded over the
+/// wire, independently of how they arrived (USB/IP, a test harness,
+/// and so on).
+pub trait UsbInterfaceHandler {
+    /// Handles one URB and returns the bytes to send back to the
+    /// remote client.
+    fn handle_urb(&mut self, urb: Urb) -> rusb::Result<Vec<u8>>;
+}
+
+/// Re-exports a locally attached cooler over USB/IP by forwarding
+/// every URB to the real device through its `Cooler` handle.
+///
+/// Control OUT requests are proxied straight through as
+/// `Cooler::write_control_raw`; interrupt IN requests read a fresh
+/// telemetry buffer off the device via `refresh_latest` before
+/// returning it. `refresh_latest`/`latest_report` are also exposed
+/// directly for callers that want the cached buffer without going
+/// through `handle_urb`.
+pub struct CoolerUrbHandler<'a, T: UsbContext> {
+    cooler: &'a mut Cooler<T>,
+    latest: Vec<u8>,
+}
+
+impl<'a, T: UsbContext> CoolerUrbHandler<'a, T> {
+    pub fn new(cooler: &'a mut Cooler<T>) -> Self {
+        CoolerUrbHandler {
+            cooler,
+            latest: vec![0u8; 64],
+        }
+    }
+
+    /// Reads a fresh telemetry report from the device and caches it,
+    /// so the next `InterruptIn` URB can be answered without a round
+    /// trip to the device.
+    pub fn refresh_latest(&mut self) -> rusb::Result<()> {
+        self.latest = self.cooler.read_raw()?;
+        Ok(())
+    }
+
+    /// Returns the cached telemetry buffer, parsed with the cooler's layout.
+    pub fn latest_report(&self) -> std::result::Result<CoolerReport, ParseError> {
+        CoolerReport::parse(&self.latest, self.cooler.layout())
+    }
+}
+
+impl<'a, T: UsbContext> UsbInterfaceHandler for CoolerUrbHandler<'a, T> {
+    fn handle_urb(&mut self, urb: Urb) -> rusb::Result<Vec<u8>> {
+        match urb {
+            Urb::ControlOut {
+                request_type,
+                request,
+                value,
+                index,
+                data,
+            } => {
+                let written = self
+                    .cooler
+                    .write_control_raw(request_type, request, value, index, &data)?;
+                Ok(vec![written as u8])
+            }
+            Urb::InterruptIn => {
+                self.refresh_latest()?;
+                Ok(self.latest.clone())
+            }
+        }
+    }
+}
+
+/// Serves `handler` to one remote client at a time over TCP, using the
+/// wire format `read_urb`/`write_response` implement below.
+///
+/// This is *not* the real USB/IP kernel wire protocol — attaching a
+/// served device to a remote `vhci-hcd` needs that exact framing, and
+/// this crate doesn't implement it (or depend on a USB/IP gadget
+/// crate). It's just enough request/response framing over TCP to
+/// drive a `UsbInterfaceHandler` from a bare-bones remote client, so a
+/// headless box with the cooler attached can serve it without one.
+/// Blocks forever, serving one client connection after another.
+pub fn serve(handler: &mut impl UsbInterfaceHandler, addr: impl ToSocketAddrs) -> Result<()> {
+    let listener = std::net::TcpListener::bind(addr)?;
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        loop {
+            let urb = match read_urb(&mut stream) {
+                Ok(urb) => urb,
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(err) => {
+                    eprintln!("usbip: dropping client after read error: {}", err);
+                    break;
+                }
+            };
+            let response = handler.handle_urb(urb)?;
+            if let Err(err) = write_response(&mut stream, &response) {
+                eprintln!("usbip: dropping client after write error: {}", err);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads one `Urb` off `reader`, tagged with a leading byte (`0` for
+/// `ControlOut`, `1` for `InterruptIn`) followed by its fields in the
+/// same order they're declared in, little-endian, with `data` prefixed
+/// by its `u16` length. A `u16` is plenty for this device's 64-byte
+/// reports; anything approaching 64KiB would desync the stream, but
+/// nothing the cooler sends or accepts gets remotely close to that.
+fn read_urb(reader: &mut impl Read) -> io::Result<Urb> {
+    match reader.read_u8()? {
+        0 => {
+            let request_type = reader.read_u8()?;
+            let request = reader.read_u8()?;
+            let value = reader.read_u16::<LittleEndian>()?;
+            let index = reader.read_u16::<LittleEndian>()?;
+            let len = reader.read_u16::<LittleEndian>()? as usize;
+            let mut data = vec![0u8; len];
+            reader.read_exact(&mut data)?;
+            Ok(Urb::ControlOut {
+                request_type,
+                request,
+                value,
+                index,
+                data,
+            })
+        }
+        1 => Ok(Urb::InterruptIn),
+        tag => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown URB tag {}", tag),
+        )),
+    }
+}
+
+/// Writes a URB response as its `u16` length followed by the bytes.
+fn write_response(writer: &mut impl Write, data: &[u8]) -> io::Result<()> {
+    writer.write_u16::<LittleEndian>(data.len() as u16)?;
+    writer.write_all(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn urb_round_trips_through_the_wire_format() {
+        let mut buf = Vec::new();
+        write_urb_for_test(
+            &mut buf,
+            &Urb::ControlOut {
+                request_type: 0x21,
+                request: 0x09,
+                value: 0x0200,
+                index: 0x0000,
+                data: vec![0x3f, 0x20, 0x01, 0x4b],
+            },
+        );
+
+        let urb = read_urb(&mut io::Cursor::new(buf)).unwrap();
+        assert_eq!(
+            urb,
+            Urb::ControlOut {
+                request_type: 0x21,
+                request: 0x09,
+                value: 0x0200,
+                index: 0x0000,
+                data: vec![0x3f, 0x20, 0x01, 0x4b],
+            }
+        );
+    }
+
+    /// Test-only mirror of the write half of `read_urb`'s format, since
+    /// nothing in the real handler needs to encode a request.
+    fn write_urb_for_test(buf: &mut Vec<u8>, urb: &Urb) {
+        match urb {
+            Urb::ControlOut {
+                request_type,
+                request,
+                value,
+                index,
+                data,
+            } => {
+                buf.write_u8(0).unwrap();
+                buf.write_u8(*request_type).unwrap();
+                buf.write_u8(*request).unwrap();
+                buf.write_u16::<LittleEndian>(*value).unwrap();
+                buf.write_u16::<LittleEndian>(*index).unwrap();
+                buf.write_u16::<LittleEndian>(data.len() as u16).unwrap();
+                buf.extend_from_slice(data);
+            }
+            Urb::InterruptIn => buf.write_u8(1).unwrap(),
+        }
+    }
+
+    /// A fixed response handler, standing in for `CoolerUrbHandler` so
+    /// this test doesn't need a real USB device.
+    struct EchoHandler;
+
+    impl UsbInterfaceHandler for EchoHandler {
+        fn handle_urb(&mut self, urb: Urb) -> rusb::Result<Vec<u8>> {
+            match urb {
+                Urb::ControlOut { .. } => Ok(vec![0xab]),
+                Urb::InterruptIn => Ok(vec![0x3f, 0x01, 0x02, 0x03]),
+            }
+        }
+    }
+
+    #[test]
+    fn serve_answers_one_client_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let urb = read_urb(&mut stream).unwrap();
+            let response = EchoHandler.handle_urb(urb).unwrap();
+            write_response(&mut stream, &response).unwrap();
+        });
+
+        let mut client = std::net::TcpStream::connect(addr).unwrap();
+        let mut request = Vec::new();
+        write_urb_for_test(&mut request, &Urb::InterruptIn);
+        client.write_all(&request).unwrap();
+
+        let len = client.read_u16::<LittleEndian>().unwrap() as usize;
+        let mut response = vec![0u8; len];
+        client.read_exact(&mut response).unwrap();
+
+        assert_eq!(response, vec![0x3f, 0x01, 0x02, 0x03]);
+        server.join().unwrap();
+    }
+}